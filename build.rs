@@ -103,7 +103,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     let features_raw = fs::read("features.toml")?;
     let feature_toml: FeatureToml = toml::from_slice(&features_raw)?;
 
-    // TODO: Add a filter that replaces `` by <code></code>
+    // Inline markdown (code spans, links, bold/italic) in feature
+    // descriptions is rendered by `util::view_markdown_with_matches` at
+    // display time instead of being pre-processed here, since it has to
+    // stay aligned with the search-match byte spans computed at runtime.
     let tera = Tera::new("templates/*")?;
     let ctx = Context::from_serialize(&feature_toml)?;
     fs::write("public/index.html", tera.render("index.html", &ctx)?)?;
@@ -125,6 +128,11 @@ fn generate_data(feature_toml: FeatureToml) -> TokenStream {
 
     let mut versions = Vec::new();
 
+    // BM25 bookkeeping: how many documents (features) each term appears in,
+    // and how many terms (words) each document is made up of.
+    let mut doc_freq: BTreeMap<String, u32> = BTreeMap::new();
+    let mut doc_lengths: Vec<u32> = Vec::new();
+
     let mut feat_idx = 0;
 
     for v in feature_toml.versions {
@@ -153,6 +161,7 @@ fn generate_data(feature_toml: FeatureToml) -> TokenStream {
             add_feature_ngrams(1, &mut monogram_index, &f, feat_idx);
             add_feature_ngrams(2, &mut bigram_index, &f, feat_idx);
             add_feature_ngrams(3, &mut trigram_index, &f, feat_idx);
+            add_feature_terms(&mut doc_freq, &mut doc_lengths, &f);
 
             feat_idx += 1;
         }
@@ -218,11 +227,48 @@ fn generate_data(feature_toml: FeatureToml) -> TokenStream {
             });
     };
 
+    let corpus_size = doc_lengths.len();
+    let avg_doc_length = if corpus_size == 0 {
+        0.0
+    } else {
+        doc_lengths.iter().copied().sum::<u32>() as f64 / corpus_size as f64
+    };
+
+    let bm25_tables = quote! {
+        /// Number of documents (features) the corpus consists of, i.e. `N`
+        /// in the BM25 formula.
+        pub const CORPUS_SIZE: usize = #corpus_size;
+        /// Average document length across the corpus, i.e. `avgdl`.
+        pub const AVERAGE_DOC_LENGTH: f64 = #avg_doc_length;
+        /// Document length (word count of title + flag + items) per
+        /// feature, indexed the same way as the n-gram posting lists.
+        pub const FEATURE_DOC_LENGTHS: &[u32] = &[#(#doc_lengths),*];
+    };
+
+    let doc_freq_insert_stmts = doc_freq.into_iter().map(|(term, df)| {
+        quote! {
+            index.insert(#term, #df);
+        }
+    });
+
+    let term_doc_frequency = quote! {
+        /// Number of features each term appears in at least once, i.e.
+        /// `df(term)` in the BM25 formula.
+        pub const TERM_DOC_FREQUENCY: once_cell::sync::Lazy<std::collections::HashMap<&'static str, u32>> =
+            once_cell::sync::Lazy::new(|| {
+                let mut index = std::collections::HashMap::new();
+                #(#doc_freq_insert_stmts)*
+                index
+            });
+    };
+
     quote! {
         #versions
         #monogram_feature_index
         #bigram_feature_index
         #trigram_feature_index
+        #bm25_tables
+        #term_doc_frequency
     }
 }
 
@@ -233,6 +279,11 @@ fn option_literal<T: ToTokens>(opt: &Option<T>) -> TokenStream {
     }
 }
 
+/// Builds the n-gram posting list for one `n` (1, 2, or 3) over `feature`'s
+/// searchable strings. Keys are lowercased first, matching the lowercasing
+/// `search::extract_search_terms` applies to every query term — otherwise a
+/// query byte n-gram could never hit a posting-list key derived from the
+/// raw, mixed-case source text.
 fn add_feature_ngrams(
     n: usize,
     index: &mut BTreeMap<Vec<u8>, BTreeSet<u16>>,
@@ -246,10 +297,45 @@ fn add_feature_ngrams(
     strings.extend(feature.items.iter());
 
     for string in strings {
-        for trigram in string.as_bytes().windows(n) {
+        let lowercased = string.to_ascii_lowercase();
+        for trigram in lowercased.as_bytes().windows(n) {
             if trigram.iter().all(|&byte| byte.is_ascii_graphic() && byte != b'`') {
                 index.entry(trigram.to_owned()).or_default().insert(idx);
             }
         }
     }
 }
+
+/// Splits `feature`'s searchable strings (title, flag, items) into
+/// lowercased words the same way `search::matching_words` splits feature
+/// text at query time, so the document frequencies and lengths recorded
+/// here line up with the term frequencies computed there.
+fn feature_words(feature: &FeatureData) -> impl Iterator<Item = String> + '_ {
+    let mut strings = vec![&feature.title];
+    if let Some(f) = &feature.flag {
+        strings.push(f);
+    }
+    strings.extend(feature.items.iter());
+
+    strings.into_iter().flat_map(|string| {
+        string
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>()
+    })
+}
+
+fn add_feature_terms(
+    doc_freq: &mut BTreeMap<String, u32>,
+    doc_lengths: &mut Vec<u32>,
+    feature: &FeatureData,
+) {
+    let words: Vec<String> = feature_words(feature).collect();
+    doc_lengths.push(words.len() as u32);
+
+    let unique_words: BTreeSet<&String> = words.iter().collect();
+    for word in unique_words {
+        *doc_freq.entry(word.clone()).or_default() += 1;
+    }
+}
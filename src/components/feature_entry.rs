@@ -0,0 +1,87 @@
+use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
+
+use crate::{
+    data::Channel,
+    data2::{FeatureData, VersionData},
+    util::view_code_with_matches,
+};
+
+#[derive(Clone, Properties)]
+pub struct Props {
+    pub feature: FeatureData,
+    pub version: Option<VersionData>,
+}
+
+pub struct FeatureEntry {
+    props: Props,
+}
+
+impl Component for FeatureEntry {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let f = &self.props.feature;
+
+        let maybe_flag = match &f.flag {
+            Some(flag) => html! { <div class="flag">{"Feature flag: "}{flag}</div> },
+            None => html! {},
+        };
+
+        let version = match &self.props.version {
+            Some(v) => html! {
+                <span class={format!("version {}", channel_class(v.channel))}>
+                    {"Rust "}{&v.number}
+                </span>
+            },
+            None => html! {},
+        };
+
+        // Item signatures (e.g. `std::iter::Iterator::flatten`) are
+        // syntax-highlighted the same way as on the matched-feature view;
+        // there are no search-match spans to overlay here, since this
+        // component receives no match data, whether it is showing the full
+        // feature list or search results.
+        let items = if f.items.is_empty() {
+            html! {}
+        } else {
+            html! {
+                <ul>
+                    { for f.items.iter().map(|item| html! {
+                        <li>{ view_code_with_matches(item, &[]) }</li>
+                    }) }
+                </ul>
+            }
+        };
+
+        html! {
+            <div class="feature box">
+                <h3>{&f.title}</h3>
+                {maybe_flag}
+                {version}
+                {items}
+            </div>
+        }
+    }
+}
+
+fn channel_class(channel: Channel) -> &'static str {
+    match channel {
+        Channel::Stable => "stable",
+        Channel::Beta => "beta",
+        Channel::Nightly => "nightly",
+    }
+}
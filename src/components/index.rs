@@ -6,13 +6,14 @@ use yew::{
         resize::{ResizeService, ResizeTask},
         timeout::{TimeoutService, TimeoutTask},
     },
-    Component, ComponentLink, Html, Properties, ShouldRender,
+    Callback, Component, ComponentLink, Html, Properties, ShouldRender,
 };
 
 use crate::{
     components::FeatureEntry,
+    data::Channel,
     data2::{FeatureData, FeatureToml, VersionData},
-    search::{extract_search_terms, run_search},
+    search::{extract_search_terms, run_search, Facets, SearchFilter},
     services::scroll::{ScrollService, ScrollTask},
     util::{document_body, window},
 };
@@ -20,8 +21,12 @@ use crate::{
 pub struct Index {
     link: ComponentLink<Self>,
     data: FeatureToml,
+    search_query: String,
+    on_query_change: Callback<String>,
     current_search_terms: Vec<String>,
+    current_search_filter: SearchFilter,
     current_search_results: Vec<(Option<VersionData>, FeatureData)>,
+    current_facets: Facets,
     items_visible: usize,
     search_scores: Vec<(u16, f64)>,
 
@@ -32,16 +37,80 @@ pub struct Index {
 
 pub enum Msg {
     Update,
+    AddFilterToken(String),
 }
 
 #[derive(Clone, Properties)]
 pub struct Props {
     pub data: FeatureToml,
     pub search_query: String,
+    /// Invoked with a new query string when the user clicks a facet bucket
+    /// in the sidebar, so the parent can reflect it back into the search
+    /// box / URL. Defaults to a no-op so existing `<Index ...>` call sites
+    /// that don't yet wire up click-to-filter keep compiling; a parent that
+    /// wants the facet buttons to actually update the search box must pass
+    /// its own callback.
+    #[prop_or_default]
+    pub on_query_change: Callback<String>,
 }
 
 const BATCH_SIZE: usize = 12;
 
+impl Index {
+    fn run_search(&mut self) {
+        let (terms, filter) = extract_search_terms(&self.search_query).unwrap_or_default();
+        let (results, facets) = run_search(&self.data, &terms, &filter, &mut self.search_scores);
+
+        self.current_search_terms = terms;
+        self.current_search_filter = filter;
+        self.current_search_results = results;
+        self.current_facets = facets;
+    }
+
+    fn is_searching(&self) -> bool {
+        !self.current_search_terms.is_empty() || !self.current_search_filter.is_empty()
+    }
+
+    fn view_facets(&self) -> Html {
+        if self.current_facets.channels.is_empty() && self.current_facets.versions.is_empty() {
+            return html! {};
+        }
+
+        let channel_token = |channel: Channel| match channel {
+            Channel::Stable => "channel:stable",
+            Channel::Beta => "channel:beta",
+            Channel::Nightly => "channel:nightly",
+        };
+
+        let channel_buttons = self.current_facets.channels.iter().map(|&(channel, count)| {
+            let token = channel_token(channel).to_owned();
+            let onclick = self.link.callback(move |_| Msg::AddFilterToken(token.clone()));
+            html! {
+                <button class="facet-bucket" onclick=onclick>
+                    { format!("{:?} ({})", channel, count) }
+                </button>
+            }
+        });
+
+        let version_buttons = self.current_facets.versions.iter().map(|(number, count)| {
+            let token = format!("version:{}", number);
+            let onclick = self.link.callback(move |_| Msg::AddFilterToken(token.clone()));
+            html! {
+                <button class="facet-bucket" onclick=onclick>
+                    { format!("Rust {} ({})", number, count) }
+                </button>
+            }
+        });
+
+        html! {
+            <div class="facets">
+                <div class="facet-group">{ for channel_buttons }</div>
+                <div class="facet-group">{ for version_buttons }</div>
+            </div>
+        }
+    }
+}
+
 impl Component for Index {
     type Message = Msg;
     type Properties = Props;
@@ -52,24 +121,26 @@ impl Component for Index {
         let _timeout_task =
             TimeoutService::new().spawn(Duration::from_secs(0), link.callback(|_| Msg::Update));
 
-        let search_terms = extract_search_terms(&props.search_query).unwrap_or_default();
-        let mut search_scores = vec![(0, 0.0); props.data.features().count()];
+        let search_scores = vec![(0, 0.0); props.data.features().count()];
 
-        let current_search_results = run_search(&props.data, &search_terms, &mut search_scores);
-        let current_search_terms = search_terms;
-
-        Self {
+        let mut this = Self {
             link,
             data: props.data,
-            current_search_terms,
-            current_search_results,
+            search_query: props.search_query,
+            on_query_change: props.on_query_change,
+            current_search_terms: Vec::new(),
+            current_search_filter: SearchFilter::default(),
+            current_search_results: Vec::new(),
+            current_facets: Facets::default(),
             items_visible: BATCH_SIZE,
             search_scores,
 
             _scroll_task,
             _resize_task,
             _timeout_task,
-        }
+        };
+        this.run_search();
+        this
     }
 
     fn update(&mut self, msg: Msg) -> ShouldRender {
@@ -90,15 +161,22 @@ impl Component for Index {
                     false
                 }
             }
+            Msg::AddFilterToken(token) => {
+                let mut query = self.search_query.clone();
+                if !query.is_empty() && !query.ends_with(' ') {
+                    query.push(' ');
+                }
+                query.push_str(&token);
+                self.on_query_change.emit(query);
+                false
+            }
         }
     }
 
     fn change(&mut self, props: Props) -> ShouldRender {
-        let search_terms = extract_search_terms(&props.search_query).unwrap_or_default();
-
-        self.current_search_results =
-            run_search(&props.data, &search_terms, &mut self.search_scores);
-        self.current_search_terms = search_terms;
+        self.search_query = props.search_query;
+        self.on_query_change = props.on_query_change;
+        self.run_search();
 
         self.items_visible = BATCH_SIZE;
         self._timeout_task = TimeoutService::new()
@@ -108,21 +186,31 @@ impl Component for Index {
     }
 
     fn view(&self) -> Html {
-        if self.current_search_terms.is_empty() {
-            let list = self.data.features().map(|(v, f)| {
-                html! {
-                    <FeatureEntry feature=f.clone() version=v.clone() />
-                }
-            });
-            html! { <div class="feature-list">{ for list.take(self.items_visible) }</div> }
+        let list = if !self.is_searching() {
+            html! {
+                <div class="feature-list">
+                    { for self.data.features().map(|(v, f)| html! {
+                        <FeatureEntry feature=f.clone() version=v.clone() />
+                    }).take(self.items_visible) }
+                </div>
+            }
         } else if self.current_search_results.is_empty() {
             html! { <div class="box muted">{"Nothing found, sorry."}</div> }
         } else {
-            let list = self.current_search_results.iter().map(|(v, f)| {
-                html! { <FeatureEntry feature=f.clone() version=v.clone() /> }
-            });
+            html! {
+                <div class="feature-list">
+                    { for self.current_search_results.iter().map(|(v, f)| html! {
+                        <FeatureEntry feature=f.clone() version=v.clone() />
+                    }).take(self.items_visible) }
+                </div>
+            }
+        };
 
-            html! { <div class="feature-list">{ for list.take(self.items_visible) }</div> }
+        html! {
+            <>
+                { self.view_facets() }
+                { list }
+            </>
         }
     }
 }
@@ -3,7 +3,7 @@ use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
 use crate::{
     components::FeatureSkel,
     features::{FeatureData, Match},
-    util::{view_text_with_matches, Span},
+    util::{view_code_with_matches, view_markdown_with_matches, view_text_with_matches, Span},
 };
 
 #[derive(Clone, Properties)]
@@ -40,7 +40,7 @@ impl Component for MatchedFeature {
         };
         let m = &self.props.match_;
 
-        let desc = view_text_with_matches(f.desc_short, &m.desc_spans);
+        let desc = view_markdown_with_matches(f.desc_short, &m.desc_spans);
 
         let maybe_flag = match f.flag {
             Some(f) => html! {
@@ -73,7 +73,7 @@ fn view_matched_items(items: &[&str], item_spans: &[Vec<Span>]) -> Html {
         .iter()
         .zip(item_spans)
         .filter(|(_, spans)| !spans.is_empty())
-        .map(|(item, spans)| html! { <li>{view_text_with_matches(item, &spans)}</li> });
+        .map(|(item, spans)| html! { <li>{view_code_with_matches(item, &spans)}</li> });
 
     let more_items_indicator = if item_spans.iter().any(|s| s.is_empty()) {
         html! { <li>{"…"}</li> }
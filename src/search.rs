@@ -0,0 +1,606 @@
+//! Free-text search over the generated feature data.
+//!
+//! Each query term is first expanded to its synonym group (see
+//! [`SYNONYM_GROUPS`]), then candidate features are retrieved cheaply
+//! through the n-gram posting lists emitted by `build.rs`
+//! (`FEATURE_MONOGRAM/BIGRAM/TRIGRAM_INDEX`) for every variant, and finally
+//! verified against the actual feature text with typo tolerance and ranked
+//! by BM25, using the per-term document frequencies and per-feature
+//! document lengths `build.rs` also emits.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::data::{
+    Channel, AVERAGE_DOC_LENGTH, CORPUS_SIZE, FEATURE_BIGRAM_INDEX, FEATURE_DOC_LENGTHS,
+    FEATURE_MONOGRAM_INDEX, FEATURE_TRIGRAM_INDEX, TERM_DOC_FREQUENCY,
+};
+use crate::data2::{FeatureData, FeatureToml, VersionData};
+
+/// BM25 term-frequency saturation parameter.
+const BM25_K1: f64 = 1.2;
+/// BM25 document-length normalization parameter.
+const BM25_B: f64 = 0.75;
+
+/// BM25 score for a single term against a single document: `tf` is the
+/// term's frequency in the document, `df` its document frequency across the
+/// whole corpus (`n` documents), `dl` the document's length, and `avg_dl`
+/// the corpus-wide average document length.
+fn bm25_score(tf: f64, df: f64, n: f64, dl: f64, avg_dl: f64) -> f64 {
+    let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+    idf * (tf * (BM25_K1 + 1.0)) / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avg_dl))
+}
+
+/// Maximum number of typos tolerated for a query term of the given byte
+/// length, modeled on the thresholds used by modern search engines: short
+/// terms must match exactly, longer terms can absorb one or two typos.
+fn typo_threshold(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// A facet filter parsed out of `channel:` / `version:` query tokens (see
+/// [`extract_search_terms`]), applied by [`run_search`] on top of the
+/// free-text terms.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchFilter {
+    pub channel: Option<Channel>,
+    pub version: Option<VersionConstraint>,
+}
+
+/// A `version:` facet token: an exact minor version, or a range bound.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VersionConstraint {
+    /// `version:1.65` — same minor version.
+    Eq(String),
+    /// `version:>=1.60`
+    AtLeast(String),
+    /// `version:<=1.60`
+    AtMost(String),
+}
+
+impl VersionConstraint {
+    fn matches(&self, number: &str) -> bool {
+        match self {
+            VersionConstraint::Eq(v) => minor_version_parts(number) == minor_version_parts(v),
+            VersionConstraint::AtLeast(v) => compare_versions(number, v) != std::cmp::Ordering::Less,
+            VersionConstraint::AtMost(v) => compare_versions(number, v) != std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+impl SearchFilter {
+    pub fn is_empty(&self) -> bool {
+        self.channel.is_none() && self.version.is_none()
+    }
+
+    fn matches(&self, version: Option<&VersionData>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let version = match version {
+            Some(version) => version,
+            None => return false,
+        };
+        if let Some(channel) = self.channel {
+            if version.channel != channel {
+                return false;
+            }
+        }
+        if let Some(constraint) = &self.version {
+            if !constraint.matches(&version.number) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Per-facet-bucket counts of matching features, for the `Index` sidebar.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Facets {
+    /// Matching feature count per release channel, in `Stable, Beta,
+    /// Nightly` order, omitting empty buckets.
+    pub channels: Vec<(Channel, usize)>,
+    /// Matching feature count per Rust minor version (e.g. `"1.65"`),
+    /// newest first.
+    pub versions: Vec<(String, usize)>,
+}
+
+/// The minor-version prefix of a Rust version number, e.g. `"1.65.2"` ->
+/// `"1.65"`.
+fn minor_version(number: &str) -> &str {
+    match number.rsplit_once('.') {
+        Some((minor, _patch)) => minor,
+        None => number,
+    }
+}
+
+/// The `(major, minor)` numeric components of a version number, ignoring any
+/// patch component. Unlike [`minor_version`] (a string prefix, meant for
+/// *display*), this is safe to call on a value that's already a minor
+/// version (e.g. a `version:` filter's `"1.65"`) as well as on a full
+/// three-component version (e.g. a feature's `"1.65.0"`) and have both
+/// compare equal — string-slicing `minor_version` a second time would
+/// instead truncate `"1.65"` down to `"1"`.
+fn minor_version_parts(number: &str) -> [u32; 2] {
+    let parts = version_parts(number);
+    [parts.first().copied().unwrap_or(0), parts.get(1).copied().unwrap_or(0)]
+}
+
+fn version_parts(number: &str) -> Vec<u32> {
+    number.split('.').filter_map(|part| part.parse().ok()).collect()
+}
+
+/// Compares two dotted version numbers component-by-component, treating a
+/// missing trailing component as `0` — so `"1.65"` and `"1.65.0"` compare
+/// equal instead of the shorter one losing purely for having fewer
+/// components (which would otherwise make `version:<=1.65` wrongly exclude
+/// the very common `"1.65.0"` feature-version form).
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut a = version_parts(a);
+    let mut b = version_parts(b);
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    a.cmp(&b)
+}
+
+fn parse_channel(value: &str) -> Option<Channel> {
+    match value.to_ascii_lowercase().as_str() {
+        "stable" => Some(Channel::Stable),
+        "beta" => Some(Channel::Beta),
+        "nightly" => Some(Channel::Nightly),
+        _ => None,
+    }
+}
+
+fn parse_version_constraint(value: &str) -> VersionConstraint {
+    if let Some(rest) = value.strip_prefix(">=") {
+        VersionConstraint::AtLeast(rest.to_string())
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        VersionConstraint::AtMost(rest.to_string())
+    } else {
+        VersionConstraint::Eq(value.to_string())
+    }
+}
+
+/// Splits a raw search query into lowercased free-text terms plus a
+/// structured [`SearchFilter`], pulling `channel:` and `version:` tokens
+/// (e.g. `channel:nightly`, `version:1.65`, `version:>=1.60`) out of the
+/// free text. Returns `None` if the query has neither terms nor filters, so
+/// callers can distinguish "no query" from "query with zero results".
+pub fn extract_search_terms(query: &str) -> Option<(Vec<String>, SearchFilter)> {
+    let mut terms = Vec::new();
+    let mut filter = SearchFilter::default();
+
+    for token in query.split_whitespace() {
+        if let Some(value) = token.strip_prefix("channel:") {
+            filter.channel = parse_channel(value);
+        } else if let Some(value) = token.strip_prefix("version:") {
+            filter.version = Some(parse_version_constraint(value));
+        } else {
+            terms.push(token.to_ascii_lowercase());
+        }
+    }
+
+    if terms.is_empty() && filter.is_empty() {
+        None
+    } else {
+        Some((terms, filter))
+    }
+}
+
+/// Groups of interchangeable Rust terminology: typing any member of a group
+/// should find documents that only contain another member. A plain const
+/// slice-of-slices so it's easy to extend — no macro or build step needed.
+const SYNONYM_GROUPS: &[&[&str]] = &[
+    &["fn", "function"],
+    &["impl", "implementation"],
+    &["async", "future"],
+    &["const", "constant"],
+    &["struct", "structure"],
+    &["enum", "enumeration"],
+    &["trait", "interface"],
+    &["dyn", "dynamic"],
+    &["macro", "macros"],
+    &["mod", "module"],
+    &["crate", "package"],
+    &["nll", "lifetimes"],
+    &["gat", "gats"],
+];
+
+/// The synonym group `term` belongs to (including `term` itself), or just
+/// `term` on its own if it isn't in any group.
+fn synonym_variants(term: &str) -> Vec<&str> {
+    for group in SYNONYM_GROUPS {
+        if group.contains(&term) {
+            return group.to_vec();
+        }
+    }
+    vec![term]
+}
+
+/// Looks up every feature index that shares at least one n-gram with
+/// `term`, unioning the posting lists of all of the term's n-grams rather
+/// than intersecting them. This is deliberately lenient: a single typo
+/// still leaves most of a word's trigrams intact, so OR-ing the lists
+/// keeps misspelled candidates around for the verification pass below,
+/// at the cost of a few extra (cheap) candidates to verify.
+fn candidates_for_term(term: &[u8]) -> HashSet<u16> {
+    let mut candidates = HashSet::new();
+
+    match term.len() {
+        0 => {}
+        1 => {
+            if let Some(list) = FEATURE_MONOGRAM_INDEX.get(&term[0]) {
+                candidates.extend(list.iter().copied());
+            }
+        }
+        2 => {
+            if let Some(list) = FEATURE_BIGRAM_INDEX.get(&[term[0], term[1]]) {
+                candidates.extend(list.iter().copied());
+            }
+        }
+        _ => {
+            for window in term.windows(3) {
+                let key = [window[0], window[1], window[2]];
+                if let Some(list) = FEATURE_TRIGRAM_INDEX.get(&key) {
+                    candidates.extend(list.iter().copied());
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Levenshtein distance between `a` and `b`, restricted to a band of width
+/// `±max_dist` around the diagonal. Cells outside the band are treated as
+/// unreachable, and a row is abandoned as soon as its minimum exceeds
+/// `max_dist`, keeping the whole computation `O(len * max_dist)` instead of
+/// `O(len^2)`. Returns `None` if the true distance exceeds `max_dist`.
+fn bounded_levenshtein(a: &[u8], b: &[u8], max_dist: usize) -> Option<usize> {
+    if (a.len().max(b.len()) - a.len().min(b.len())) > max_dist {
+        return None;
+    }
+
+    const INF: usize = usize::MAX / 2;
+    let t = max_dist;
+    let n = a.len();
+    let m = b.len();
+
+    let mut prev = vec![INF; m + 1];
+    for (j, cell) in prev.iter_mut().enumerate().take(t.min(m) + 1) {
+        *cell = j;
+    }
+
+    for i in 1..=n {
+        let lo = i.saturating_sub(t);
+        let hi = (i + t).min(m);
+
+        let mut cur = vec![INF; m + 1];
+        if lo == 0 {
+            cur[0] = i;
+        }
+
+        let mut row_min = cur[lo];
+        for j in lo.max(1)..=hi {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let deletion = prev[j] + 1;
+            let insertion = cur[j - 1] + 1;
+            let substitution = prev[j - 1] + cost;
+            cur[j] = deletion.min(insertion).min(substitution);
+            row_min = row_min.min(cur[j]);
+        }
+
+        if row_min > t {
+            return None;
+        }
+        prev = cur;
+    }
+
+    Some(prev[m]).filter(|&dist| dist <= t)
+}
+
+/// Counts how many words of `haystack` fall within `term`'s typo budget,
+/// i.e. this haystack's contribution to `term`'s BM25 term frequency, along
+/// with the distance and text of the closest such word (the word is used to
+/// look up its document frequency and for highlighting, since it's the word
+/// that actually matched, not necessarily the literal query term). Words are
+/// split on non-alphanumeric bytes so e.g. `::` and `<>` in item signatures
+/// don't get folded into neighbouring words.
+fn matching_words<'h>(term: &[u8], haystack: &'h str, max_dist: usize) -> (u32, Option<(usize, &'h str)>) {
+    let mut tf = 0;
+    let mut closest: Option<(usize, &str)> = None;
+
+    for word in haystack
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if let Some(dist) = bounded_levenshtein(term, word.to_ascii_lowercase().as_bytes(), max_dist) {
+            tf += 1;
+            let is_closer = match closest {
+                Some((best, _)) => dist < best,
+                None => true,
+            };
+            if is_closer {
+                closest = Some((dist, word));
+            }
+        }
+    }
+
+    (tf, closest)
+}
+
+/// Matches `variants` (a term's synonym group, see [`synonym_variants`])
+/// against `haystacks`, returning the term frequency and matched word of
+/// whichever variant matches best (smallest edit distance; ties broken by
+/// higher term frequency) — i.e. an exact match on one synonym always beats
+/// a fuzzy match on another. Variants are scored independently rather than
+/// summed, so a feature mentioning both "fn" and "function" doesn't get
+/// double-counted for a query of either.
+fn best_synonym_match<'h>(variants: &[&str], haystacks: &[&'h str]) -> Option<(u32, &'h str)> {
+    let mut best: Option<(u32, usize, &str)> = None;
+
+    for variant in variants {
+        let variant_bytes = variant.as_bytes();
+        let max_dist = typo_threshold(variant_bytes.len());
+
+        let mut tf = 0u32;
+        let mut closest: Option<(usize, &str)> = None;
+        for haystack in haystacks {
+            let (haystack_tf, haystack_closest) = matching_words(variant_bytes, haystack, max_dist);
+            tf += haystack_tf;
+            if let Some((dist, word)) = haystack_closest {
+                closest = match closest {
+                    Some((best_dist, _)) if best_dist <= dist => closest,
+                    _ => Some((dist, word)),
+                };
+            }
+        }
+
+        if tf == 0 {
+            continue;
+        }
+        let (dist, word) = closest.expect("tf > 0 implies a closest match was recorded");
+
+        let is_better = match best {
+            Some((best_tf, best_dist, _)) => dist < best_dist || (dist == best_dist && tf > best_tf),
+            None => true,
+        };
+        if is_better {
+            best = Some((tf, dist, word));
+        }
+    }
+
+    best.map(|(tf, _, word)| (tf, word))
+}
+
+/// Runs a search for `terms`, narrowed by `filter`, over every feature in
+/// `data`. Writes a `(matched_term_count, score)` pair per feature into
+/// `scores` (indexed the same way as the n-gram posting lists), and returns
+/// the matching features (most relevant first, by BM25 score — see the
+/// module docs) alongside a [`Facets`] breakdown, for the `Index` sidebar,
+/// of how the *text*-matching features distribute across channels and
+/// versions. Each facet dimension is computed against `filter` narrowed by
+/// every *other* dimension (so a channel bucket's count already reflects an
+/// active `version:` filter, and vice versa), the standard "facet excludes
+/// its own dimension" pattern — otherwise a bucket's printed count wouldn't
+/// match the results you'd get by clicking it.
+///
+/// A feature is only text-matched if *every* term matched somewhere in its
+/// title, flag, or items, within that term's typo budget. An empty `terms`
+/// list matches every feature, so a bare facet filter (e.g. just
+/// `channel:nightly`) still narrows the full list.
+pub fn run_search(
+    data: &FeatureToml,
+    terms: &[String],
+    filter: &SearchFilter,
+    scores: &mut Vec<(u16, f64)>,
+) -> (Vec<(Option<VersionData>, FeatureData)>, Facets) {
+    let features: Vec<(Option<VersionData>, FeatureData)> = data.features().collect();
+    scores.resize(features.len(), (0, 0.0));
+    for score in scores.iter_mut() {
+        *score = (0, 0.0);
+    }
+
+    for term in terms {
+        let variants = synonym_variants(term);
+
+        let mut candidates = HashSet::new();
+        for variant in &variants {
+            candidates.extend(candidates_for_term(variant.as_bytes()));
+        }
+
+        for idx in candidates {
+            let (_, feature) = &features[idx as usize];
+            let haystacks: Vec<&str> = std::iter::once(feature.title.as_str())
+                .chain(feature.flag.as_deref())
+                .chain(feature.items.iter().map(String::as_str))
+                .collect();
+
+            let (tf, word) = match best_synonym_match(&variants, &haystacks) {
+                Some(best) => best,
+                None => continue,
+            };
+
+            let df = TERM_DOC_FREQUENCY
+                .get(word.to_ascii_lowercase().as_str())
+                .copied()
+                .unwrap_or(0) as f64;
+            let dl = FEATURE_DOC_LENGTHS[idx as usize] as f64;
+
+            let bm25 = bm25_score(tf as f64, df, CORPUS_SIZE as f64, dl, AVERAGE_DOC_LENGTH);
+
+            let (matched_terms, score) = &mut scores[idx as usize];
+            *matched_terms += 1;
+            *score += bm25;
+        }
+    }
+
+    let text_matching: Vec<usize> = if terms.is_empty() {
+        (0..features.len()).collect()
+    } else {
+        (0..features.len())
+            .filter(|&idx| scores[idx].0 as usize == terms.len())
+            .collect()
+    };
+
+    let facets = compute_facets(&features, &text_matching, filter);
+
+    let mut matching: Vec<usize> = text_matching
+        .into_iter()
+        .filter(|&idx| filter.matches(features[idx].0.as_ref()))
+        .collect();
+
+    matching.sort_by(|&a, &b| {
+        scores[b]
+            .1
+            .partial_cmp(&scores[a].1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let results = matching.into_iter().map(|idx| features[idx].clone()).collect();
+    (results, facets)
+}
+
+/// Builds a [`Facets`] breakdown of `matching` against `filter`, computing
+/// each dimension's bucket counts against the *other* dimension's filter
+/// only — e.g. channel counts are narrowed by `filter.version` but not by
+/// `filter.channel` itself, so that clicking a channel bucket doesn't just
+/// reselect the one already active.
+fn compute_facets(
+    features: &[(Option<VersionData>, FeatureData)],
+    matching: &[usize],
+    filter: &SearchFilter,
+) -> Facets {
+    let channel_facet_filter = SearchFilter {
+        channel: None,
+        version: filter.version.clone(),
+    };
+    let version_facet_filter = SearchFilter {
+        channel: filter.channel,
+        version: None,
+    };
+
+    let mut stable = 0;
+    let mut beta = 0;
+    let mut nightly = 0;
+    let mut version_counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for &idx in matching {
+        let version = match &features[idx].0 {
+            Some(version) => version,
+            None => continue,
+        };
+
+        if channel_facet_filter.matches(Some(version)) {
+            match version.channel {
+                Channel::Stable => stable += 1,
+                Channel::Beta => beta += 1,
+                Channel::Nightly => nightly += 1,
+            }
+        }
+
+        if version_facet_filter.matches(Some(version)) {
+            *version_counts
+                .entry(minor_version(&version.number).to_string())
+                .or_default() += 1;
+        }
+    }
+
+    let mut channels = Vec::new();
+    if stable > 0 {
+        channels.push((Channel::Stable, stable));
+    }
+    if beta > 0 {
+        channels.push((Channel::Beta, beta));
+    }
+    if nightly > 0 {
+        channels.push((Channel::Nightly, nightly));
+    }
+
+    let mut versions: Vec<(String, usize)> = version_counts.into_iter().collect();
+    versions.sort_by(|a, b| compare_versions(&b.0, &a.0));
+
+    Facets { channels, versions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_eq_matches_already_minor_filter_value() {
+        let constraint = parse_version_constraint("1.65");
+        assert!(constraint.matches("1.65.0"));
+        assert!(constraint.matches("1.65.1")); // same minor, different patch still matches
+        assert!(!constraint.matches("1.64.0"));
+    }
+
+    #[test]
+    fn version_eq_matches_full_filter_value_too() {
+        let constraint = parse_version_constraint("1.65.0");
+        assert!(constraint.matches("1.65.3"));
+    }
+
+    #[test]
+    fn version_at_least_and_at_most() {
+        assert!(parse_version_constraint(">=1.60").matches("1.65.0"));
+        assert!(!parse_version_constraint(">=1.70").matches("1.65.0"));
+        assert!(parse_version_constraint("<=1.70").matches("1.65.0"));
+        assert!(!parse_version_constraint("<=1.60").matches("1.65.0"));
+    }
+
+    #[test]
+    fn at_most_and_at_least_treat_a_missing_patch_component_as_zero() {
+        // "1.65" and "1.65.0" denote the same release; a boundary check
+        // against one shouldn't exclude the other purely for having fewer
+        // dotted components.
+        assert!(parse_version_constraint("<=1.65").matches("1.65.0"));
+        assert!(parse_version_constraint(">=1.65").matches("1.65.0"));
+        assert!(parse_version_constraint("<=1.65.0").matches("1.65"));
+        assert!(parse_version_constraint(">=1.65.0").matches("1.65"));
+    }
+
+    #[test]
+    fn bm25_score_rewards_rarer_terms_and_shorter_documents() {
+        // A term appearing in fewer documents (lower df) scores higher.
+        let common = bm25_score(1.0, 50.0, 100.0, 20.0, 20.0);
+        let rare = bm25_score(1.0, 5.0, 100.0, 20.0, 20.0);
+        assert!(rare > common);
+
+        // A shorter-than-average document scores higher for the same tf/df.
+        let short_doc = bm25_score(1.0, 10.0, 100.0, 5.0, 20.0);
+        let long_doc = bm25_score(1.0, 10.0, 100.0, 40.0, 20.0);
+        assert!(short_doc > long_doc);
+
+        // Zero term frequency contributes nothing.
+        assert_eq!(bm25_score(0.0, 10.0, 100.0, 20.0, 20.0), 0.0);
+    }
+
+    #[test]
+    fn bounded_levenshtein_finds_distance_within_budget() {
+        assert_eq!(bounded_levenshtein(b"fn", b"fn", 0), Some(0));
+        assert_eq!(bounded_levenshtein(b"fn", b"f", 1), Some(1));
+        assert_eq!(bounded_levenshtein(b"function", b"functoin", 2), Some(2));
+        assert_eq!(bounded_levenshtein(b"fn", b"xyz", 1), None);
+    }
+
+    #[test]
+    fn best_synonym_match_ties_broken_by_higher_term_frequency() {
+        // Both "fn" and "function" match exactly (distance 0), but
+        // "function" matches three times versus "fn"'s one. Since "fn" is
+        // tried first (group order), the documented tie-break — higher `tf`
+        // wins on an exact-distance tie — is the only thing that can make
+        // "function" (tried second) override it.
+        let variants = synonym_variants("fn");
+        let haystacks = ["fn", "function function function"];
+        let (tf, word) = best_synonym_match(&variants, &haystacks).expect("should match");
+        assert_eq!(word, "function");
+        assert_eq!(tf, 3);
+    }
+}
@@ -0,0 +1,563 @@
+//! Small helpers shared across components: DOM accessors, the
+//! match-highlighting renderer, and a lightweight Rust token classifier used
+//! to syntax-highlight feature item signatures.
+
+use web_sys::{Document, HtmlElement, Window};
+use yew::{html, Html};
+
+pub fn window() -> Window {
+    web_sys::window().expect("no global `window` exists")
+}
+
+pub fn document() -> Document {
+    window().document().expect("window has no document")
+}
+
+pub fn document_body() -> HtmlElement {
+    document().body().expect("document has no body")
+}
+
+/// A byte range `[start, end)` into a raw source string, naming the part
+/// that should be highlighted as a search match. Spans passed to the
+/// `view_*_with_matches` renderers below must be sorted and non-overlapping.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Renders `text`, wrapping the byte ranges named by `spans` in `<mark>`.
+pub fn view_text_with_matches(text: &str, spans: &[Span]) -> Html {
+    let mut nodes = Vec::new();
+    let mut cursor = 0;
+
+    for span in spans {
+        if span.start > cursor {
+            nodes.push(html! { <>{ &text[cursor..span.start] }</> });
+        }
+        nodes.push(html! { <mark>{ &text[span.start..span.end] }</mark> });
+        cursor = span.end;
+    }
+    if cursor < text.len() {
+        nodes.push(html! { <>{ &text[cursor..] }</> });
+    }
+
+    html! { <>{ for nodes }</> }
+}
+
+/// Token categories emitted by [`classify_rust_tokens`], matched to CSS
+/// classes so themes can color them, modeled on rustdoc's own highlighter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Ident,
+    Type,
+    Lifetime,
+    Number,
+    Str,
+    Comment,
+    Operator,
+    Punct,
+    Whitespace,
+}
+
+impl TokenKind {
+    fn css_class(self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "tok-keyword",
+            TokenKind::Ident => "tok-ident",
+            TokenKind::Type => "tok-type",
+            TokenKind::Lifetime => "tok-lifetime",
+            TokenKind::Number => "tok-number",
+            TokenKind::Str => "tok-string",
+            TokenKind::Comment => "tok-comment",
+            TokenKind::Operator => "tok-operator",
+            TokenKind::Punct => "tok-punct",
+            TokenKind::Whitespace => "tok-whitespace",
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "try", "type", "union", "unsafe", "use", "where", "while",
+];
+
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+    "u128", "usize", "f32", "f64",
+];
+
+/// Classifies `code` into a sequence of non-overlapping, contiguous
+/// `(Span, TokenKind)` runs via a single-pass state machine over the bytes.
+/// This is deliberately not a full lexer — it's sized for the short item
+/// signatures this app displays (e.g. `std::iter::Iterator::flatten`,
+/// `impl Trait`), but it does handle raw/byte strings, `'a` lifetimes vs
+/// `'c'` char literals, and the `::`/`->`/`=>` multi-byte operators.
+pub fn classify_rust_tokens(code: &str) -> Vec<(Span, TokenKind)> {
+    let bytes = code.as_bytes();
+    let len = bytes.len();
+    let mut tokens = Vec::with_capacity(len / 4);
+    let mut i = 0;
+
+    while i < len {
+        let start = i;
+        let rest = &code[i..];
+
+        let kind = if bytes[i].is_ascii_whitespace() {
+            while i < len && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            TokenKind::Whitespace
+        } else if rest.starts_with("//") {
+            while i < len && bytes[i] != b'\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if rest.starts_with("/*") {
+            i += scan_block_comment(rest);
+            TokenKind::Comment
+        } else if is_raw_string_start(rest) {
+            i += scan_raw_string(rest);
+            TokenKind::Str
+        } else if rest.starts_with("b\"") {
+            i += 2 + scan_string_body(&code[i + 2..]);
+            TokenKind::Str
+        } else if rest.starts_with("b'") {
+            i += 2 + scan_escaped_quote_body(&code[i + 2..]);
+            TokenKind::Str
+        } else if bytes[i] == b'"' {
+            i += 1 + scan_string_body(&code[i + 1..]);
+            TokenKind::Str
+        } else if bytes[i] == b'\'' {
+            let (consumed, kind) = scan_lifetime_or_char(rest);
+            i += consumed;
+            kind
+        } else if bytes[i].is_ascii_digit() {
+            while i < len && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'.') {
+                i += 1;
+            }
+            TokenKind::Number
+        } else if bytes[i] == b'_' || bytes[i].is_ascii_alphabetic() {
+            while i < len && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            classify_word(&code[start..i])
+        } else if rest.starts_with("::") || rest.starts_with("->") || rest.starts_with("=>") {
+            i += 2;
+            TokenKind::Operator
+        } else if is_operator_byte(bytes[i]) {
+            i += 1;
+            TokenKind::Operator
+        } else {
+            i += 1;
+            TokenKind::Punct
+        };
+
+        tokens.push((Span { start, end: i }, kind));
+    }
+
+    tokens
+}
+
+fn classify_word(word: &str) -> TokenKind {
+    if KEYWORDS.contains(&word) {
+        TokenKind::Keyword
+    } else if PRIMITIVES.contains(&word) || word.starts_with(|c: char| c.is_uppercase()) {
+        TokenKind::Type
+    } else {
+        TokenKind::Ident
+    }
+}
+
+fn is_operator_byte(b: u8) -> bool {
+    matches!(
+        b,
+        b'+' | b'-' | b'*' | b'/' | b'%' | b'^' | b'!' | b'&' | b'|' | b'=' | b'<' | b'>' | b'.'
+            | b':'
+            | b'@'
+            | b'?'
+            | b'~'
+    )
+}
+
+/// Distinguishes `'a` lifetimes from `'c'`/`'\n'` char literals: a `'`
+/// directly followed by an escape sequence, or by exactly one byte and a
+/// closing `'`, is a char literal; otherwise it's a lifetime (or a lone
+/// `'`, passed through as punctuation).
+fn scan_lifetime_or_char(s: &str) -> (usize, TokenKind) {
+    let bytes = s.as_bytes();
+
+    if bytes.get(1) == Some(&b'\\') {
+        let consumed = 2 + scan_escaped_quote_body(&s[2..]);
+        return (consumed, TokenKind::Str);
+    }
+    if bytes.get(2) == Some(&b'\'') {
+        return (3, TokenKind::Str);
+    }
+
+    let mut i = 1;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+        i += 1;
+    }
+    if i == 1 {
+        (1, TokenKind::Punct)
+    } else {
+        (i, TokenKind::Lifetime)
+    }
+}
+
+/// Consumes an escape sequence (e.g. `n'`, `x41'`, `u{1F600}'`) up to and
+/// including its closing `'`. `s` starts right after the opening `'\`, or,
+/// for a byte literal, right after the opening `b'`; either way everything
+/// up to and including the next `'` is consumed.
+fn scan_escaped_quote_body(s: &str) -> usize {
+    match s.find('\'') {
+        Some(pos) => pos + 1,
+        None => s.len(),
+    }
+}
+
+/// Consumes a double-quoted string body up to and including its closing
+/// `"`, honoring backslash escapes. `s` starts right after the opening `"`.
+/// Never returns past `s.len()`, even for a body that ends mid-escape (a
+/// lone trailing `\`) with no closing quote at all.
+fn scan_string_body(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i = (i + 2).min(bytes.len()),
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    i
+}
+
+/// Consumes a (possibly nested) block comment up to and including its final
+/// closing `*/`. `s` starts at the opening `/*`. Compares raw bytes rather
+/// than re-slicing `s` at `i` — `s[i..]` would panic as soon as `i` landed on
+/// a UTF-8 continuation byte, which arbitrary comment text (e.g. `/* café
+/// */`) can easily do.
+fn scan_block_comment(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = 2;
+    let mut depth = 1;
+    while i < bytes.len() && depth > 0 {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    i
+}
+
+fn is_raw_string_start(s: &str) -> bool {
+    let s = s.strip_prefix('b').unwrap_or(s);
+    let s = match s.strip_prefix('r') {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let hashes = s.bytes().take_while(|&b| b == b'#').count();
+    s.as_bytes().get(hashes) == Some(&b'"')
+}
+
+/// Consumes a raw (optionally byte-) string up to and including its closing
+/// `"` plus the matching number of `#`s, e.g. `r#"..."#` or `br"..."`. `s`
+/// starts at the leading `b`/`r`. Never returns past `s.len()`: a `"` isn't
+/// accepted as the close unless the *full* `hashes` count of trailing `#`
+/// bytes genuinely follows it — checking with `.take(hashes)` alone would be
+/// vacuously satisfied by a too-short remainder (e.g. `r#"abc"`, one `#`
+/// short of closing), wrongly reporting a close past the string's actual end.
+fn scan_raw_string(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = usize::from(bytes[0] == b'b') + 1; // skip optional 'b' and the 'r'
+    let hash_start = i;
+    while bytes.get(i) == Some(&b'#') {
+        i += 1;
+    }
+    let hashes = i - hash_start;
+    i += 1; // opening quote
+
+    loop {
+        match bytes.get(i) {
+            None => return bytes.len(),
+            Some(b'"') => {
+                let trailing = &bytes[i + 1..];
+                if trailing.len() >= hashes && trailing[..hashes].iter().all(|&b| b == b'#') {
+                    return (i + 1 + hashes).min(bytes.len());
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// A run of `raw[start..end]` and how it should be wrapped when rendered.
+/// The range always names *content* bytes (markdown delimiters themselves
+/// are never part of a run), so match `Span`s — which are byte offsets into
+/// `raw` — can be re-projected onto a run by simply clipping them to its
+/// `start..end` and rebasing, with no further remapping needed.
+enum RunKind<'a> {
+    Plain,
+    Code,
+    Bold,
+    Italic,
+    Link(&'a str),
+}
+
+struct Run<'a> {
+    start: usize,
+    end: usize,
+    kind: RunKind<'a>,
+}
+
+/// Finds the next occurrence of `delim` after the opening one at `raw[at..]`
+/// and, if found with non-empty content in between, returns
+/// `(content_start, content_end, resume_at)`.
+fn try_delimited(raw: &str, at: usize, delim: &str) -> Option<(usize, usize, usize)> {
+    let content_start = at + delim.len();
+    let close_rel = raw[content_start..].find(delim)?;
+    if close_rel == 0 {
+        return None;
+    }
+    let content_end = content_start + close_rel;
+    Some((content_start, content_end, content_end + delim.len()))
+}
+
+/// Matches `[text](url)` starting at `raw[at..]` (`raw[at] == '['`) and
+/// returns `(text_start, text_end, url_start, url_end, resume_at)`.
+fn try_link(raw: &str, at: usize) -> Option<(usize, usize, usize, usize, usize)> {
+    let text_start = at + 1;
+    let text_end = text_start + raw[text_start..].find(']')?;
+    let after_bracket = text_end + 1;
+    if raw.as_bytes().get(after_bracket) != Some(&b'(') {
+        return None;
+    }
+    let url_start = after_bracket + 1;
+    let url_end = url_start + raw[url_start..].find(')')?;
+    Some((text_start, text_end, url_start, url_end, url_end + 1))
+}
+
+/// Parses `raw`'s inline markdown (code spans, links, `**bold**`/`*italic*`)
+/// into an ordered sequence of runs. A description with no markdown syntax
+/// parses into a single `Plain` run spanning the whole string.
+fn parse_inline_markdown(raw: &str) -> Vec<Run<'_>> {
+    let bytes = raw.as_bytes();
+    let len = bytes.len();
+    let mut runs = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+
+    while i < len {
+        let marked = match bytes[i] {
+            b'`' => try_delimited(raw, i, "`").map(|(cs, ce, resume)| (cs, ce, resume, RunKind::Code)),
+            b'*' if raw[i..].starts_with("**") => {
+                try_delimited(raw, i, "**").map(|(cs, ce, resume)| (cs, ce, resume, RunKind::Bold))
+            }
+            b'*' => try_delimited(raw, i, "*").map(|(cs, ce, resume)| (cs, ce, resume, RunKind::Italic)),
+            b'[' => try_link(raw, i)
+                .map(|(ts, te, us, ue, resume)| (ts, te, resume, RunKind::Link(&raw[us..ue]))),
+            _ => None,
+        };
+
+        match marked {
+            Some((content_start, content_end, resume_at, kind)) => {
+                if plain_start < i {
+                    runs.push(Run { start: plain_start, end: i, kind: RunKind::Plain });
+                }
+                runs.push(Run { start: content_start, end: content_end, kind });
+                i = resume_at;
+                plain_start = i;
+            }
+            None => i += 1,
+        }
+    }
+
+    if plain_start < len {
+        runs.push(Run { start: plain_start, end: len, kind: RunKind::Plain });
+    }
+
+    runs
+}
+
+/// Clips `spans` to `start..end` and rebases them to be relative to `start`,
+/// dropping any span (or part of a span) that falls outside the range —
+/// which is exactly what should happen to a highlight that straddled a
+/// markdown delimiter now stripped from the rendered output.
+fn clip_spans(spans: &[Span], start: usize, end: usize) -> Vec<Span> {
+    spans
+        .iter()
+        .filter_map(|span| {
+            let clipped_start = span.start.max(start);
+            let clipped_end = span.end.min(end);
+            (clipped_start < clipped_end).then(|| Span {
+                start: clipped_start - start,
+                end: clipped_end - start,
+            })
+        })
+        .collect()
+}
+
+/// Renders `raw` with inline markdown (code spans, links, `**bold**`,
+/// `*italic*`) turned into real Yew `Html`, while still honoring `spans` —
+/// byte offsets into the *raw, unrendered* string — as search-match
+/// highlights. Descriptions with no markdown render identically to
+/// [`view_text_with_matches`].
+pub fn view_markdown_with_matches(raw: &str, spans: &[Span]) -> Html {
+    let nodes = parse_inline_markdown(raw).into_iter().map(|run| {
+        let text = &raw[run.start..run.end];
+        let local_spans = clip_spans(spans, run.start, run.end);
+        let content = view_text_with_matches(text, &local_spans);
+
+        match run.kind {
+            RunKind::Plain => content,
+            RunKind::Code => html! { <code>{content}</code> },
+            RunKind::Bold => html! { <strong>{content}</strong> },
+            RunKind::Italic => html! { <em>{content}</em> },
+            RunKind::Link(href) => html! {
+                <a href={href.to_string()} target="_blank" rel="noopener noreferrer">{content}</a>
+            },
+        }
+    });
+
+    html! { <>{ for nodes }</> }
+}
+
+/// Renders `code` as syntax-highlighted, search-match-highlighted HTML:
+/// each byte is classified by [`classify_rust_tokens`] *and* checked
+/// against `spans`, with the two boundary sets merged so a token can be
+/// simultaneously colored (`<span class="tok-...">`) and highlighted
+/// (`<mark>`) even when a match straddles a token boundary.
+pub fn view_code_with_matches(code: &str, spans: &[Span]) -> Html {
+    let tokens = classify_rust_tokens(code);
+
+    let mut boundaries: Vec<usize> = std::iter::once(0)
+        .chain(std::iter::once(code.len()))
+        .chain(tokens.iter().flat_map(|(span, _)| [span.start, span.end]))
+        .chain(spans.iter().flat_map(|span| [span.start, span.end]))
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut token_idx = 0;
+    let mut match_idx = 0;
+    let mut nodes = Vec::new();
+
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if start == end {
+            continue;
+        }
+
+        while token_idx < tokens.len() && tokens[token_idx].0.end <= start {
+            token_idx += 1;
+        }
+        let kind = tokens
+            .get(token_idx)
+            .map_or(TokenKind::Punct, |(_, kind)| *kind);
+
+        while match_idx < spans.len() && spans[match_idx].end <= start {
+            match_idx += 1;
+        }
+        let is_match = match spans.get(match_idx) {
+            Some(span) => span.start <= start && end <= span.end,
+            None => false,
+        };
+
+        let segment = html! { <span class={kind.css_class()}>{ &code[start..end] }</span> };
+        nodes.push(if is_match {
+            html! { <mark>{segment}</mark> }
+        } else {
+            segment
+        });
+    }
+
+    html! { <>{ for nodes }</> }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_body_clamps_on_trailing_backslash() {
+        // `"abc\` with no closing quote and a dangling escape at EOF.
+        let body = "abc\\";
+        assert_eq!(scan_string_body(body), body.len());
+    }
+
+    #[test]
+    fn string_body_consumes_escapes_and_closing_quote() {
+        let rest = "ab\\\"cd\" after";
+        assert_eq!(scan_string_body(rest), "ab\\\"cd\"".len());
+    }
+
+    #[test]
+    fn raw_string_requires_the_full_hash_count_to_close() {
+        // One `#` short of closing `r#"..."#` — must not report a close.
+        let rest = "r#\"abc\"";
+        assert_eq!(scan_raw_string(rest), rest.len());
+    }
+
+    #[test]
+    fn raw_string_closes_with_matching_hash_count() {
+        let rest = "r#\"abc\"#trailing";
+        assert_eq!(scan_raw_string(rest), "r#\"abc\"#".len());
+    }
+
+    #[test]
+    fn classify_rust_tokens_never_panics_on_truncated_raw_string() {
+        classify_rust_tokens("r#\"abc\"");
+        classify_rust_tokens("\"abc\\");
+    }
+
+    #[test]
+    fn block_comment_handles_non_ascii_bytes_without_panicking() {
+        let tokens = classify_rust_tokens("/* café */ fn");
+        assert_eq!(tokens[0].1, TokenKind::Comment);
+        assert_eq!(&"/* café */ fn"[tokens[0].0.start..tokens[0].0.end], "/* café */");
+    }
+
+    #[test]
+    fn clip_spans_drops_spans_outside_the_run_and_rebases_the_rest() {
+        let spans = vec![Span { start: 2, end: 4 }, Span { start: 10, end: 12 }];
+        // Run covers [0, 6): the first span survives rebased to the run's
+        // own coordinates, the second (entirely outside) is dropped.
+        let clipped = clip_spans(&spans, 0, 6);
+        assert_eq!(clipped, vec![Span { start: 2, end: 4 }]);
+
+        // Run covers [3, 9): the first span is partially clipped and
+        // rebased, the second still falls entirely outside.
+        let clipped = clip_spans(&spans, 3, 9);
+        assert_eq!(clipped, vec![Span { start: 0, end: 1 }]);
+    }
+
+    #[test]
+    fn parse_inline_markdown_plain_text_is_a_single_run() {
+        let runs = parse_inline_markdown("no markup here");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].start, 0);
+        assert_eq!(runs[0].end, "no markup here".len());
+    }
+
+    #[test]
+    fn parse_inline_markdown_splits_code_span_into_its_own_run() {
+        let raw = "see `std::fmt` for details";
+        let runs = parse_inline_markdown(raw);
+        let code_run = runs
+            .iter()
+            .find(|r| matches!(r.kind, RunKind::Code))
+            .expect("a code run should have been parsed");
+        assert_eq!(&raw[code_run.start..code_run.end], "std::fmt");
+    }
+}